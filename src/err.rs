@@ -1,4 +1,6 @@
+use std::error;
 use std::ffi;
+use std::fmt;
 use std::io;
 #[cfg(unix)]
 use std::str;
@@ -44,9 +46,29 @@ impl LookupError {
   pub fn error_num(&self) -> i32 {
     self.err_num
   }
+
+  /// Whether this failure is transient (`EAI_AGAIN` / `WSATRY_AGAIN`),
+  /// meaning a later retry of the same lookup may succeed.
+  pub fn is_temporary(&self) -> bool {
+    matches!(self.kind, LookupErrorKind::Again)
+  }
+
+  /// Whether this failure is non-recoverable (`EAI_FAIL`), meaning a
+  /// retry of the same lookup is not expected to succeed.
+  pub fn is_unrecoverable(&self) -> bool {
+    matches!(self.kind, LookupErrorKind::Fail)
+  }
+
+  /// Whether this failure means the name or host simply has no such
+  /// data (`EAI_NONAME` / `EAI_NODATA`), as opposed to a transient or
+  /// system-level error.
+  pub fn is_not_found(&self) -> bool {
+    matches!(self.kind, LookupErrorKind::NoName | LookupErrorKind::NoData)
+  }
 }
 
 #[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
 pub enum LookupErrorKind {
   /// Temporary failure in name resolution.
   Again,
@@ -68,6 +90,13 @@ pub enum LookupErrorKind {
   Memory,
   /// System error returned in `errno'.
   System,
+  /// Argument buffer overflow.
+  Overflow,
+  /// Address family for `nodename' not supported. Only ever produced on
+  /// the portable fallback (`not(unix), not(windows)`) path: `EAI_ADDRFAMILY`
+  /// is a legacy BSD code that neither glibc/musl nor Windows define, so
+  /// real Unix and Windows targets never construct this variant.
+  AddrFamily,
   /// Either a generic C error, or an unknown result
   /// code.
   IO,
@@ -77,7 +106,21 @@ impl LookupErrorKind {
   #[cfg(all(not(windows), not(unix)))]
   /// Create a `LookupErrorKind` from a `gai` error.
   fn new(err: i32) -> Self {
-    LookupErrorKind::IO
+    use self::fallback_gai as c;
+    match err {
+      c::EAI_AGAIN => LookupErrorKind::Again,
+      c::EAI_BADFLAGS => LookupErrorKind::Badflags,
+      c::EAI_FAIL => LookupErrorKind::Fail,
+      c::EAI_FAMILY => LookupErrorKind::Family,
+      c::EAI_MEMORY => LookupErrorKind::Memory,
+      c::EAI_NONAME => LookupErrorKind::NoName,
+      c::EAI_NODATA if c::EAI_NODATA != c::EAI_NONAME => LookupErrorKind::NoData,
+      c::EAI_SERVICE => LookupErrorKind::Service,
+      c::EAI_SOCKTYPE => LookupErrorKind::Socktype,
+      c::EAI_ADDRFAMILY => LookupErrorKind::AddrFamily,
+      c::EAI_OVERFLOW => LookupErrorKind::Overflow,
+      _ => LookupErrorKind::IO,
+    }
   }
 
   #[cfg(unix)]
@@ -97,6 +140,11 @@ impl LookupErrorKind {
       c::EAI_SERVICE => LookupErrorKind::Service,
       c::EAI_SOCKTYPE => LookupErrorKind::Socktype,
       c::EAI_SYSTEM => LookupErrorKind::System,
+      c::EAI_OVERFLOW => LookupErrorKind::Overflow,
+      // `EAI_ADDRFAMILY` is a legacy BSD code that glibc, musl and most
+      // other libcs no longer define (folded into `EAI_FAMILY`); the
+      // `libc` crate only exposes it on a handful of niche `unix`
+      // targets, so it is not matched here and falls through to `IO`.
       _ => LookupErrorKind::IO,
     }
   }
@@ -115,11 +163,27 @@ impl LookupErrorKind {
       e::WSANO_DATA => LookupErrorKind::NoData,
       e::WSATYPE_NOT_FOUND => LookupErrorKind::Service,
       e::WSAESOCKTNOSUPPORT => LookupErrorKind::Socktype,
+      // getnameinfo signals that the supplied host/service buffer was
+      // too small to hold the result with WSAEFAULT; there is no
+      // Windows equivalent of EAI_ADDRFAMILY.
+      e::WSAEFAULT => LookupErrorKind::Overflow,
       _ => LookupErrorKind::IO,
     }
   }
 }
 
+impl fmt::Display for LookupError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.inner)
+  }
+}
+
+impl error::Error for LookupError {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    Some(&self.inner)
+  }
+}
+
 impl From<LookupError> for io::Error {
   fn from(err: LookupError) -> io::Error {
     err.inner
@@ -143,21 +207,52 @@ impl From<ffi::NulError> for LookupError {
   }
 }
 
+#[cfg(all(not(windows), not(unix)))]
+/// Standard `gai` error constants, for platforms whose libc does not
+/// expose them (and thus has no `gai_strerror` to ask for a message).
+mod fallback_gai {
+  pub const EAI_AGAIN: i32 = 2;
+  pub const EAI_BADFLAGS: i32 = 3;
+  pub const EAI_FAIL: i32 = 4;
+  pub const EAI_FAMILY: i32 = 5;
+  pub const EAI_MEMORY: i32 = 6;
+  pub const EAI_NODATA: i32 = 7;
+  pub const EAI_NONAME: i32 = 8;
+  pub const EAI_SERVICE: i32 = 9;
+  pub const EAI_SOCKTYPE: i32 = 10;
+  pub const EAI_ADDRFAMILY: i32 = 11;
+  pub const EAI_OVERFLOW: i32 = 14;
+}
+
 #[cfg(all(not(windows), not(unix)))]
 /// Given a gai error, return an `std::io::Error` with
 /// the appropriate error message. Note `0` is not an
 /// error, but will still map to an error
 pub(crate) fn gai_err_to_io_err(err: i32) -> io::Error {
-  match (err) {
-    0 => io::Error::new(
+  use self::fallback_gai as c;
+
+  let detail = match err {
+    0 => return io::Error::new(
       io::ErrorKind::Other,
       "address information lookup success"
     ),
-    _ => io::Error::new(
-      io::ErrorKind::Other,
-      "failed to lookup address information"
-    ),
-  }
+    c::EAI_AGAIN => "Temporary failure in name resolution",
+    c::EAI_BADFLAGS => "Invalid flags for ai_flags",
+    c::EAI_FAIL => "Non-recoverable failure in name resolution",
+    c::EAI_FAMILY => "ai_family not supported",
+    c::EAI_MEMORY => "Memory allocation failure",
+    c::EAI_NODATA if c::EAI_NODATA != c::EAI_NONAME => "No address associated with hostname",
+    c::EAI_NONAME => "Name or service not known",
+    c::EAI_SERVICE => "Service not supported for ai_socktype",
+    c::EAI_SOCKTYPE => "ai_socktype not supported",
+    c::EAI_ADDRFAMILY => "Address family for hostname not supported",
+    c::EAI_OVERFLOW => "Argument buffer overflow",
+    _ => "failed to lookup address information",
+  };
+
+  io::Error::new(io::ErrorKind::Other,
+    &format!("failed to lookup address information: {}", detail)[..]
+  )
 }
 
 #[cfg(unix)]
@@ -203,3 +298,76 @@ pub(crate) fn gai_err_to_io_err(err: i32) -> io::Error {
     }
   }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+  use super::*;
+  use std::error::Error;
+
+  #[test]
+  // musl's gai_strerror wording differs enough from glibc's (e.g.
+  // EAI_AGAIN is "Try again" rather than "Temporary failure in name
+  // resolution") that there is no meaningful common substring to assert
+  // on, so this is only run against glibc.
+  #[cfg(target_env = "gnu")]
+  fn display_renders_gai_message_for_each_kind() {
+    let cases = [
+      (libc::EAI_AGAIN, "Temporary failure in name resolution"),
+      (libc::EAI_BADFLAGS, "Bad value for ai_flags"),
+      (libc::EAI_FAIL, "Non-recoverable failure in name res"),
+      (libc::EAI_FAMILY, "not supported"),
+      (libc::EAI_MEMORY, "Memory allocation failure"),
+      (libc::EAI_NONAME, "Name or service not known"),
+      (libc::EAI_SERVICE, "not supported"),
+      (libc::EAI_SOCKTYPE, "not supported"),
+    ];
+
+    for (err, expected_fragment) in cases.iter() {
+      let lookup_err = LookupError::new(*err);
+      let rendered = format!("{}", lookup_err);
+      assert!(
+        rendered.contains(expected_fragment),
+        "expected {:?} to contain {:?}",
+        rendered,
+        expected_fragment
+      );
+    }
+  }
+
+  #[test]
+  fn source_returns_inner_io_error_for_system_failure() {
+    let lookup_err = LookupError::new(libc::EAI_SYSTEM);
+    assert!(matches!(lookup_err.kind(), LookupErrorKind::System));
+
+    let source = lookup_err
+      .source()
+      .and_then(|e| e.downcast_ref::<io::Error>())
+      .expect("EAI_SYSTEM should carry an io::Error source");
+    // `gai_err_to_io_err` builds the EAI_SYSTEM case from
+    // `io::Error::last_os_error()`, which always carries a raw OS error
+    // code; every other kind is built from a plain message and carries
+    // none, so this is specific to EAI_SYSTEM rather than tautological.
+    assert!(source.raw_os_error().is_some());
+  }
+
+  #[test]
+  fn is_temporary_only_for_again() {
+    assert!(LookupError::new(libc::EAI_AGAIN).is_temporary());
+    assert!(!LookupError::new(libc::EAI_FAIL).is_temporary());
+  }
+
+  #[test]
+  fn is_unrecoverable_only_for_fail() {
+    assert!(LookupError::new(libc::EAI_FAIL).is_unrecoverable());
+    assert!(!LookupError::new(libc::EAI_AGAIN).is_unrecoverable());
+  }
+
+  #[test]
+  fn is_not_found_for_noname_and_nodata() {
+    // -5 is the platform code this crate maps to `NoData` on Unix,
+    // since it is not exposed by the `libc` crate.
+    assert!(LookupError::new(libc::EAI_NONAME).is_not_found());
+    assert!(LookupError::new(-5).is_not_found());
+    assert!(!LookupError::new(libc::EAI_FAIL).is_not_found());
+  }
+}
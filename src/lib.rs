@@ -0,0 +1,13 @@
+//! Thin wrapper around `getaddrinfo`, plus an error type for the result
+//! codes of `getaddrinfo` and `getnameinfo`.
+
+mod err;
+// `getaddrinfo`/`freeaddrinfo` are POSIX APIs; the winsock equivalents
+// differ enough (no `addrinfo`/`sockaddr_in6` from `libc` on Windows)
+// that this module is unix-only until a winsock path is written.
+#[cfg(unix)]
+mod lookup;
+
+pub use err::{LookupError, LookupErrorKind};
+#[cfg(unix)]
+pub use lookup::{getaddrinfo, AddrInfoIter};
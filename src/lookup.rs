@@ -0,0 +1,206 @@
+use std::ffi::CString;
+use std::io;
+use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::ptr;
+
+use libc::{addrinfo, c_int, freeaddrinfo, sockaddr, sockaddr_in, sockaddr_in6, socklen_t, AF_INET, AF_INET6};
+
+use crate::err::LookupError;
+
+/// Resolve `host`/`service` via `getaddrinfo`, returning an `AddrInfoIter`
+/// over the results. A `getaddrinfo` failure is not returned directly;
+/// instead it is surfaced from the first call to the iterator's `next()`,
+/// so callers always get an `AddrInfoIter` and decide lazily whether to
+/// look at the error.
+pub fn getaddrinfo(host: Option<&str>, service: Option<&str>) -> AddrInfoIter {
+  match getaddrinfo_raw(host, service) {
+    Ok(res) => AddrInfoIter::new(res),
+    Err(err) => AddrInfoIter::failed(err),
+  }
+}
+
+fn getaddrinfo_raw(host: Option<&str>, service: Option<&str>) -> Result<*mut addrinfo, LookupError> {
+  let c_host = host.map(CString::new).transpose()?;
+  let c_service = service.map(CString::new).transpose()?;
+
+  let host_ptr = c_host.as_ref().map_or(ptr::null(), |s| s.as_ptr());
+  let service_ptr = c_service.as_ref().map_or(ptr::null(), |s| s.as_ptr());
+
+  let mut res: *mut addrinfo = ptr::null_mut();
+  let ret = unsafe { libc::getaddrinfo(host_ptr, service_ptr, ptr::null(), &mut res) };
+  LookupError::match_gai_error(ret)?;
+  Ok(res)
+}
+
+/// Lazily walks the `addrinfo` linked list returned by `getaddrinfo`,
+/// yielding one resolved `SocketAddr` per node as `next()` is called.
+///
+/// Modeled on the C++ `addrinfo` const-iterator pattern: it holds a
+/// pointer to the current node and advances through `ai_next` until it
+/// reaches null. If `getaddrinfo` itself failed, the first call to
+/// `next()` surfaces that `LookupError` rather than silently producing
+/// an empty iterator. The backing `addrinfo` allocation is freed via
+/// `freeaddrinfo` when the iterator is dropped, even on early
+/// termination.
+pub struct AddrInfoIter {
+  // The head of the list, kept only so it can be passed to
+  // `freeaddrinfo` on drop (it stays set after the list is exhausted);
+  // null if the list never existed (lookup failure).
+  orig: *mut addrinfo,
+  // The node `next()` will yield, or null once exhausted.
+  cur: *mut addrinfo,
+  // The `getaddrinfo` failure, if the lookup itself did not succeed.
+  err: Option<LookupError>,
+}
+
+// Safe to move between threads: the raw pointers only ever get
+// dereferenced through `&mut self`, so there is no shared mutable state
+// that would also require `Sync`.
+unsafe impl Send for AddrInfoIter {}
+
+impl AddrInfoIter {
+  /// Build an iterator over a successful `getaddrinfo` result list.
+  /// Ownership of `res` (and the duty to `freeaddrinfo` it) moves here.
+  pub(crate) fn new(res: *mut addrinfo) -> Self {
+    AddrInfoIter {
+      orig: res,
+      cur: res,
+      err: None,
+    }
+  }
+
+  /// Build an iterator that surfaces `err` on the first call to
+  /// `next()`, for a `getaddrinfo` call that failed outright.
+  pub(crate) fn failed(err: LookupError) -> Self {
+    AddrInfoIter {
+      orig: ptr::null_mut(),
+      cur: ptr::null_mut(),
+      err: Some(err),
+    }
+  }
+}
+
+impl Iterator for AddrInfoIter {
+  type Item = Result<SocketAddr, LookupError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Some(err) = self.err.take() {
+      return Some(Err(err));
+    }
+
+    if self.cur.is_null() {
+      return None;
+    }
+
+    let cur = unsafe { &*self.cur };
+    self.cur = cur.ai_next;
+    let addr = unsafe { sockaddr_to_addr(cur.ai_addr, cur.ai_addrlen) };
+    Some(addr.map_err(LookupError::from))
+  }
+}
+
+impl Drop for AddrInfoIter {
+  fn drop(&mut self) {
+    if !self.orig.is_null() {
+      unsafe { freeaddrinfo(self.orig) };
+    }
+  }
+}
+
+/// Convert a raw `sockaddr` returned by `getaddrinfo` into a `SocketAddr`.
+unsafe fn sockaddr_to_addr(addr: *const sockaddr, len: socklen_t) -> io::Result<SocketAddr> {
+  match (*addr).sa_family as c_int {
+    AF_INET => {
+      assert!(len as usize >= mem::size_of::<sockaddr_in>());
+      let sa = &*(addr as *const sockaddr_in);
+      let ip = Ipv4Addr::from(u32::from_be(sa.sin_addr.s_addr));
+      let port = u16::from_be(sa.sin_port);
+      Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+    }
+    AF_INET6 => {
+      assert!(len as usize >= mem::size_of::<sockaddr_in6>());
+      let sa = &*(addr as *const sockaddr_in6);
+      let ip = Ipv6Addr::from(sa.sin6_addr.s6_addr);
+      let port = u16::from_be(sa.sin6_port);
+      Ok(SocketAddr::V6(SocketAddrV6::new(
+        ip,
+        port,
+        sa.sin6_flowinfo,
+        sa.sin6_scope_id,
+      )))
+    }
+    _ => Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      "unsupported address family returned from getaddrinfo",
+    )),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sockaddr_in_with_port(port: u16) -> sockaddr_in {
+    let mut sa: sockaddr_in = unsafe { mem::zeroed() };
+    sa.sin_family = AF_INET as libc::sa_family_t;
+    sa.sin_port = port.to_be();
+    sa.sin_addr.s_addr = u32::from(Ipv4Addr::new(127, 0, 0, 1)).to_be();
+    sa
+  }
+
+  #[test]
+  fn failed_yields_the_error_once_then_stops() {
+    let mut iter = AddrInfoIter::failed(LookupError::new(libc::EAI_AGAIN));
+    assert!(iter.next().expect("first call should surface the error").is_err());
+    assert!(iter.next().is_none());
+  }
+
+  #[test]
+  fn walks_ai_next_chain_lazily() {
+    let sa1 = sockaddr_in_with_port(80);
+    let sa2 = sockaddr_in_with_port(443);
+
+    let mut node2: addrinfo = unsafe { mem::zeroed() };
+    node2.ai_family = AF_INET;
+    node2.ai_addr = &sa2 as *const sockaddr_in as *mut sockaddr;
+    node2.ai_addrlen = mem::size_of::<sockaddr_in>() as socklen_t;
+    node2.ai_next = ptr::null_mut();
+
+    let mut node1: addrinfo = unsafe { mem::zeroed() };
+    node1.ai_family = AF_INET;
+    node1.ai_addr = &sa1 as *const sockaddr_in as *mut sockaddr;
+    node1.ai_addrlen = mem::size_of::<sockaddr_in>() as socklen_t;
+    node1.ai_next = &mut node2;
+
+    // Hand-built, stack-allocated list: `orig` is left null so `Drop`
+    // never calls `freeaddrinfo` on non-heap memory.
+    let mut iter = AddrInfoIter {
+      orig: ptr::null_mut(),
+      cur: &mut node1,
+      err: None,
+    };
+
+    assert_eq!(
+      iter.next().unwrap().unwrap(),
+      SocketAddr::from(([127, 0, 0, 1], 80))
+    );
+    assert_eq!(
+      iter.next().unwrap().unwrap(),
+      SocketAddr::from(([127, 0, 0, 1], 443))
+    );
+    assert!(iter.next().is_none());
+  }
+
+  #[test]
+  fn getaddrinfo_localhost_resolves_and_frees_on_drop() {
+    let mut iter = getaddrinfo(Some("localhost"), None);
+    let first = iter
+      .next()
+      .expect("localhost should resolve to at least one address")
+      .expect("localhost lookup should not fail");
+    assert!(first.ip().is_loopback());
+    // Dropping mid-iteration exercises `freeaddrinfo` on a real,
+    // OS-allocated list after early termination.
+  }
+}